@@ -0,0 +1,168 @@
+//! A background daemon that owns the timer loop for a session so it keeps running
+//! after the launching terminal disconnects. Controlled over a Unix domain socket by
+//! short, newline-terminated text commands (`PAUSE`, `RESUME`, `STOP`, `STATUS`).
+
+use crate::{init_db, play_sound, record_session, Mode};
+use chrono::{DateTime, Utc};
+use notify_rust::Notification;
+use rusqlite::{Connection, Result};
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Path to the control socket the daemon listens on and clients connect to.
+fn socket_path() -> PathBuf {
+    std::env::temp_dir().join("tomatl.sock")
+}
+
+struct DaemonState {
+    mode: Mode,
+    remaining: Duration,
+    paused: bool,
+    stopped: bool,
+    started_at: DateTime<Utc>,
+    elapsed_minutes: f32,
+}
+
+/// Runs the daemon: ticks the countdown once a second, accepts control connections on
+/// the Unix socket, and records the session (full or partial, if stopped early) once
+/// it ends.
+pub fn run_daemon(mode: Mode, duration: Duration) -> Result<()> {
+    let conn = Connection::open("focus.db")?;
+    init_db(&conn)?;
+
+    let path = socket_path();
+    let _ = std::fs::remove_file(&path);
+    let listener = UnixListener::bind(&path).expect("failed to bind tomatl control socket");
+
+    let state = Arc::new(Mutex::new(DaemonState {
+        mode,
+        remaining: duration,
+        paused: false,
+        stopped: false,
+        started_at: Utc::now(),
+        elapsed_minutes: 0.0,
+    }));
+
+    {
+        let state = Arc::clone(&state);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let state = Arc::clone(&state);
+                thread::spawn(move || handle_client(stream, state));
+            }
+        });
+    }
+
+    // `true` once the interval ran its full course; `false` if a client `STOP`ped it
+    // early, in which case we shouldn't claim the session "completed".
+    let finished_naturally = loop {
+        thread::sleep(Duration::from_secs(1));
+        let mut s = state.lock().unwrap();
+        if s.stopped {
+            break false;
+        }
+        if s.paused {
+            continue;
+        }
+        s.remaining = s.remaining.saturating_sub(Duration::from_secs(1));
+        s.elapsed_minutes += 1.0 / 60.0;
+        if s.remaining.is_zero() {
+            break true;
+        }
+    };
+
+    let (mode, elapsed_minutes, started_at) = {
+        let s = state.lock().unwrap();
+        (s.mode, s.elapsed_minutes, s.started_at)
+    };
+
+    if finished_naturally {
+        Notification::new()
+            .summary("Timer up!")
+            .body(&format!("Your {} session is complete.", mode.as_str()))
+            .show()
+            .unwrap();
+        if let Err(e) = play_sound(None, 1, false) {
+            eprintln!("Error playing sound: {}", e);
+        }
+    }
+
+    record_session(&conn, started_at, elapsed_minutes, mode)?;
+    let _ = std::fs::remove_file(&path);
+    Ok(())
+}
+
+/// Handles one client connection: reads a single command line and writes back a
+/// single reply line.
+fn handle_client(stream: UnixStream, state: Arc<Mutex<DaemonState>>) {
+    let mut reader = BufReader::new(match stream.try_clone() {
+        Ok(s) => s,
+        Err(_) => return,
+    });
+    let mut line = String::new();
+    if reader.read_line(&mut line).is_err() {
+        return;
+    }
+
+    let reply = {
+        let mut s = state.lock().unwrap();
+        match line.trim() {
+            "PAUSE" => {
+                s.paused = true;
+                "paused".to_string()
+            }
+            "RESUME" => {
+                s.paused = false;
+                "resumed".to_string()
+            }
+            "STOP" => {
+                s.stopped = true;
+                s.paused = false;
+                "stopping".to_string()
+            }
+            "STATUS" => format!(
+                "{} {} {}",
+                s.mode.as_str(),
+                s.remaining.as_secs(),
+                if s.paused { "paused" } else { "running" }
+            ),
+            other => format!("unknown command: {other}"),
+        }
+    };
+
+    let mut writer = stream;
+    let _ = writeln!(writer, "{reply}");
+}
+
+/// Sends a single command line to the running daemon and returns its reply line.
+fn send_command(command: &str) -> std::io::Result<String> {
+    let mut stream = UnixStream::connect(socket_path())?;
+    writeln!(stream, "{command}")?;
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply)?;
+    Ok(reply.trim().to_string())
+}
+
+/// Asks the running daemon to pause its countdown.
+pub fn pause() -> std::io::Result<String> {
+    send_command("PAUSE")
+}
+
+/// Asks the running daemon to resume its countdown.
+pub fn resume() -> std::io::Result<String> {
+    send_command("RESUME")
+}
+
+/// Asks the running daemon to stop, recording the elapsed time as a partial session.
+pub fn stop() -> std::io::Result<String> {
+    send_command("STOP")
+}
+
+/// Asks the running daemon for its current mode and remaining time.
+pub fn status() -> std::io::Result<String> {
+    send_command("STATUS")
+}
@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, Utc};
 use clap::Parser;
 use colored::Colorize;
 use figlet_rs::FIGfont;
@@ -6,9 +6,13 @@ use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use notify_rust::Notification;
 use rodio::{Decoder, OutputStream, Sink};
 use rusqlite::{Connection, Result, params};
+use std::path::PathBuf;
 use std::{thread, time::Duration};
 
-#[derive(clap::ValueEnum, Clone, Debug)]
+mod daemon;
+mod tui;
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
 enum Mode {
     Focus,
     Rest,
@@ -27,8 +31,197 @@ impl Mode {
 #[derive(Parser, Debug)]
 #[command(name = "tomatl-cli", about = "Manage focus and rest sessions")]
 struct Cli {
-    mode: Mode,
-    minutes: f32,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(clap::Args, Debug)]
+struct SoundOpts {
+    /// Play this file instead of the embedded default completion sound
+    #[arg(long)]
+    sound: Option<PathBuf>,
+    /// Repeat the completion sound this many times
+    #[arg(long = "loop-sound", default_value_t = 1)]
+    loop_sound: u32,
+    /// Skip the completion sound entirely
+    #[arg(long)]
+    silent: bool,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// Run a single focus session
+    Focus {
+        /// e.g. `25`, `25m`, `1h30m`, `90s`
+        #[arg(value_parser = parse_duration)]
+        duration: Duration,
+        /// Show a full-screen countdown instead of the default progress bar
+        #[arg(long)]
+        tui: bool,
+        #[command(flatten)]
+        sound_opts: SoundOpts,
+    },
+    /// Run a single rest session
+    Rest {
+        /// e.g. `5`, `5m`, `1h30m`, `90s`
+        #[arg(value_parser = parse_duration)]
+        duration: Duration,
+        /// Show a full-screen countdown instead of the default progress bar
+        #[arg(long)]
+        tui: bool,
+        #[command(flatten)]
+        sound_opts: SoundOpts,
+    },
+    /// Run a full Pomodoro cycle: repeated focus/rest intervals topped off with a long rest
+    Cycle {
+        /// Length of each focus interval, e.g. `25m`
+        #[arg(long, default_value = "25m", value_parser = parse_duration)]
+        work: Duration,
+        /// Length of the short rest between focus intervals, e.g. `5m`
+        #[arg(long = "short-rest", default_value = "5m", value_parser = parse_duration)]
+        short_rest: Duration,
+        /// Length of the long rest after the last interval in a cycle, e.g. `15m`
+        #[arg(long = "long-rest", default_value = "15m", value_parser = parse_duration)]
+        long_rest: Duration,
+        /// Number of focus intervals per cycle before the long rest
+        #[arg(long, default_value_t = 4)]
+        cycles: u32,
+        /// Show a full-screen countdown instead of the default progress bar
+        #[arg(long)]
+        tui: bool,
+        #[command(flatten)]
+        sound_opts: SoundOpts,
+    },
+    /// Summarize the focus sessions recorded so far
+    Stats,
+    /// Launch a session as a detached background daemon, controllable via
+    /// pause/resume/stop/status
+    Start {
+        mode: Mode,
+        /// e.g. `25`, `25m`, `1h30m`, `90s`
+        #[arg(value_parser = parse_duration)]
+        duration: Duration,
+    },
+    /// Pause the running background daemon's countdown
+    Pause,
+    /// Resume the running background daemon's countdown
+    Resume,
+    /// Stop the running background daemon, recording the partial session
+    Stop,
+    /// Report the running background daemon's current mode and remaining time
+    Status,
+    /// Internal: runs the daemon loop itself. Spawned by `start`, not meant to be
+    /// invoked directly.
+    #[command(hide = true, name = "daemon-internal")]
+    DaemonInternal {
+        mode: Mode,
+        #[arg(value_parser = parse_duration)]
+        duration: Duration,
+    },
+}
+
+/// Parses a human-friendly duration like `25m`, `1h30m`, or `90s`. A bare number with
+/// no unit suffix (`25`, `0.5`) is interpreted as minutes, for backward compatibility
+/// with the original `minutes: f32` argument.
+fn parse_duration(s: &str) -> std::result::Result<Duration, String> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err("duration must not be empty".to_string());
+    }
+
+    if let Ok(minutes) = s.parse::<f32>() {
+        if !minutes.is_finite() || minutes < 0.0 {
+            return Err(format!(
+                "duration '{s}' must be a finite, non-negative number of minutes"
+            ));
+        }
+        return Ok(Duration::from_secs_f32(minutes * 60.0));
+    }
+
+    let mut total_secs = 0u64;
+    let mut number = String::new();
+    for ch in s.chars() {
+        if ch.is_ascii_digit() {
+            number.push(ch);
+            continue;
+        }
+        if number.is_empty() {
+            return Err(format!("duration '{s}' has a unit with no preceding number"));
+        }
+        let value: u64 = number
+            .parse()
+            .map_err(|_| format!("invalid number in duration '{s}'"))?;
+        number.clear();
+        total_secs += match ch {
+            'h' => value * 3600,
+            'm' => value * 60,
+            's' => value,
+            other => return Err(format!("unknown duration unit '{other}' in '{s}'")),
+        };
+    }
+    if !number.is_empty() {
+        return Err(format!("duration '{s}' is missing a unit suffix"));
+    }
+
+    Ok(Duration::from_secs(total_secs))
+}
+
+#[cfg(test)]
+mod parse_duration_tests {
+    use super::*;
+
+    #[test]
+    fn bare_number_is_minutes() {
+        assert_eq!(parse_duration("25").unwrap(), Duration::from_secs(25 * 60));
+    }
+
+    #[test]
+    fn minutes_suffix() {
+        assert_eq!(parse_duration("25m").unwrap(), Duration::from_secs(25 * 60));
+    }
+
+    #[test]
+    fn hours_and_minutes() {
+        assert_eq!(
+            parse_duration("1h30m").unwrap(),
+            Duration::from_secs(3600 + 30 * 60)
+        );
+    }
+
+    #[test]
+    fn seconds_suffix() {
+        assert_eq!(parse_duration("90s").unwrap(), Duration::from_secs(90));
+    }
+
+    #[test]
+    fn empty_is_rejected() {
+        assert!(parse_duration("").is_err());
+    }
+
+    #[test]
+    fn unit_with_no_number_is_rejected() {
+        assert!(parse_duration("m").is_err());
+    }
+
+    #[test]
+    fn trailing_number_with_no_unit_is_rejected() {
+        assert!(parse_duration("1h30").is_err());
+    }
+
+    #[test]
+    fn unknown_unit_is_rejected() {
+        assert!(parse_duration("25x").is_err());
+    }
+
+    #[test]
+    fn infinite_minutes_is_rejected() {
+        assert!(parse_duration("inf").is_err());
+    }
+
+    #[test]
+    fn negative_minutes_is_rejected() {
+        assert!(parse_duration("-5").is_err());
+    }
 }
 
 /// Initializes (or migrates) the database: creates `sessions` table if it doesn't exist.
@@ -38,107 +231,383 @@ fn init_db(conn: &Connection) -> Result<()> {
         CREATE TABLE IF NOT EXISTS sessions (
             id             INTEGER PRIMARY KEY AUTOINCREMENT,
             start_iso      TEXT NOT NULL,
-            minutes        FLOAT NOT NULL
+            minutes        FLOAT NOT NULL,
+            mode           TEXT NOT NULL DEFAULT 'focus'
         );
         "#,
     )?;
+    // Older databases were created before the `mode` column existed; add it if missing
+    // and ignore the error if it's already there.
+    let _ = conn.execute(
+        "ALTER TABLE sessions ADD COLUMN mode TEXT NOT NULL DEFAULT 'focus'",
+        [],
+    );
     Ok(())
 }
 
-/// Inserts a new focus session into `sessions`.
-/// 
+/// Inserts a new session into `sessions`.
+///
 /// - `start` is the UTC timestamp when the session began.
 /// - `minutes` is the length of that session in.
-fn record_session(conn: &Connection, start: DateTime<Utc>, minutes: f32) -> Result<()> {
+/// - `mode` is whether this was a focus or a rest interval.
+fn record_session(conn: &Connection, start: DateTime<Utc>, minutes: f32, mode: Mode) -> Result<()> {
     let start_iso = start.to_rfc3339(); // e.g. "2025-05-30T14:23:00+00:00"
     conn.execute(
-        "INSERT INTO sessions (start_iso, minutes) VALUES (?1, ?2)",
-        params![start_iso, minutes],
+        "INSERT INTO sessions (start_iso, minutes, mode) VALUES (?1, ?2, ?3)",
+        params![start_iso, minutes, mode.as_str()],
     )?;
     Ok(())
 }
 
-fn main() -> Result<()> {
-    let conn = Connection::open("focus.db")?;
-    init_db(&conn)?;
-    
-    let args = Cli::parse();
-    let mode = &args.mode.as_str();
-    let minutes = args.minutes;
+/// How a session interval ended.
+enum SessionEnd {
+    /// The interval ran to completion, or the user skipped straight to the end.
+    Finished,
+    /// The user asked to quit (only reachable from the `--tui` front-end).
+    Quit,
+}
+
+/// Runs one focus-or-rest interval to completion: either the line-based indicatif
+/// spinner/progress bar, or (with `tui`) a full-screen ratatui countdown. Either way,
+/// a completed interval fires a desktop notification and sound and is recorded as a
+/// `sessions` row.
+fn run_session(
+    conn: &Connection,
+    mode: Mode,
+    duration: Duration,
+    tui: bool,
+    sound_opts: &SoundOpts,
+) -> Result<SessionEnd> {
+    let mode_str = mode.as_str();
     let now = Utc::now();
+    let mut minutes = duration.as_secs_f32() / 60.0;
 
-    // 1) ASCII-art header
-    let font = FIGfont::standard().unwrap();
-    let figure = font.convert(mode).unwrap();
-    println!("\n{}\n", figure.to_string().cyan().bold());
+    if tui {
+        let tui::RunResult { outcome, elapsed } =
+            tui::run(mode, duration).expect("tui countdown failed");
+        match outcome {
+            tui::Outcome::Completed => {}
+            // Skipping straight to completion still counts as finishing the
+            // interval, so it gets the same notification/sound/record as a
+            // completed one below — credited for the time actually elapsed.
+            tui::Outcome::Skipped => minutes = elapsed.as_secs_f32() / 60.0,
+            tui::Outcome::Quit => return Ok(SessionEnd::Quit),
+        }
+    } else {
+        // 1) ASCII-art header
+        let font = FIGfont::standard().unwrap();
+        let figure = font.convert(mode_str).unwrap();
+        println!("\n{}\n", figure.to_string().cyan().bold());
 
-    // 2) Subheader with emoji
-    println!(
-        "{}\n",
-        format!(
-            "Starting a {} session for {} minutes ⏱️",
-            mode.green(),
-            minutes
-        )
-        .magenta()
-        .bold()
-    );
+        // 2) Subheader with emoji
+        println!(
+            "{}\n",
+            format!(
+                "Starting a {} session for {} minutes ⏱️",
+                mode_str.green(),
+                minutes
+            )
+            .magenta()
+            .bold()
+        );
 
-    // 3) Spinner + progress bar
-    let total_secs = (minutes * 60.0) as u64;
-    let mp = MultiProgress::new();
+        // 3) Spinner + progress bar
+        let total_secs = duration.as_secs();
+        let mp = MultiProgress::new();
 
-    let spinner = mp.add(ProgressBar::new_spinner());
-    spinner.set_style(
-        ProgressStyle::default_spinner()
-            .tick_strings(&["⣾", "⣽", "⣻", "⢿", "⡿", "⣟", "⣯", "⣷"])
-            .template("{spinner:.blue} {msg}")
-            .unwrap(),
-    );
-    spinner.set_message("Good luck!");
-    spinner.enable_steady_tick(Duration::from_millis(80));
+        let spinner = mp.add(ProgressBar::new_spinner());
+        spinner.set_style(
+            ProgressStyle::default_spinner()
+                .tick_strings(&["⣾", "⣽", "⣻", "⢿", "⡿", "⣟", "⣯", "⣷"])
+                .template("{spinner:.blue} {msg}")
+                .unwrap(),
+        );
+        spinner.set_message("Good luck!");
+        spinner.enable_steady_tick(Duration::from_millis(80));
 
-    let pb = mp.add(ProgressBar::new(total_secs));
-    pb.set_style(
-        ProgressStyle::with_template("{bar:40.cyan/blue} {pos:>3}/{len:3} sec • ETA {eta_precise}")
+        let pb = mp.add(ProgressBar::new(total_secs));
+        pb.set_style(
+            ProgressStyle::with_template(
+                "{bar:40.cyan/blue} {pos:>3}/{len:3} sec • ETA {eta_precise}",
+            )
             .unwrap()
             .progress_chars("█▇▆▅▄▃▂▁ "),
-    );
+        );
 
-    // 4) Run!
-    for _ in 0..total_secs {
-        pb.inc(1);
-        thread::sleep(Duration::from_secs(1));
+        // 4) Run!
+        for _ in 0..total_secs {
+            pb.inc(1);
+            thread::sleep(Duration::from_secs(1));
+        }
+        spinner.finish_and_clear();
+        pb.finish_with_message("🎉 Done!");
     }
-    spinner.finish_and_clear();
-    pb.finish_with_message("🎉 Done!");
 
     // 5) Desktop notification
     Notification::new()
         .summary("Timer up!")
-        .body(&format!("Your {} session is complete.", mode))
+        .body(&format!("Your {} session is complete.", mode_str))
         .show()
         .unwrap();
 
     // 6) Play sound
-    if let Err(e) = play_sound() {
+    if let Err(e) = play_sound(
+        sound_opts.sound.as_deref(),
+        sound_opts.loop_sound,
+        sound_opts.silent,
+    ) {
         eprintln!("Error playing sound: {}", e);
     }
-    record_session(&conn, now, minutes)?;
+    record_session(conn, now, minutes, mode)?;
+    Ok(SessionEnd::Finished)
+}
+
+/// Runs the classic Pomodoro cycle: `cycles` focus intervals, each followed by a short
+/// rest (except the last), then a long rest, looping forever until the process is
+/// stopped (or, under `--tui`, until the user quits the current interval).
+fn run_cycle(
+    conn: &Connection,
+    work: Duration,
+    short_rest: Duration,
+    long_rest: Duration,
+    cycles: u32,
+    tui: bool,
+    sound_opts: &SoundOpts,
+) -> Result<()> {
+    loop {
+        for i in 0..cycles {
+            if let SessionEnd::Quit = run_session(conn, Mode::Focus, work, tui, sound_opts)? {
+                return Ok(());
+            }
+            if i + 1 < cycles {
+                if let SessionEnd::Quit =
+                    run_session(conn, Mode::Rest, short_rest, tui, sound_opts)?
+                {
+                    return Ok(());
+                }
+            }
+        }
+        if let SessionEnd::Quit = run_session(conn, Mode::Rest, long_rest, tui, sound_opts)? {
+            return Ok(());
+        }
+    }
+}
+
+/// Aggregates over the focus sessions recorded in `sessions`. Every focus row counts,
+/// including partial ones recorded when a daemon is stopped early or a `--tui`
+/// interval is skipped.
+struct Stats {
+    today_minutes: f32,
+    week_minutes: f32,
+    all_time_minutes: f32,
+    completed_sessions: u32,
+    average_minutes: f32,
+    last_session: Option<DateTime<Utc>>,
+}
+
+/// Reads every recorded focus session and aggregates totals for today, this week,
+/// and all-time, along with the timestamp of the most recent one.
+fn compute_stats(conn: &Connection) -> Result<Stats> {
+    let mut stmt = conn.prepare("SELECT start_iso, minutes FROM sessions WHERE mode = 'focus'")?;
+    let rows = stmt.query_map(params![], |row| {
+        let start_iso: String = row.get(0)?;
+        let minutes: f32 = row.get(1)?;
+        Ok((start_iso, minutes))
+    })?;
+
+    let now = Utc::now();
+    let today_start = now.date_naive().and_hms_opt(0, 0, 0).unwrap().and_utc();
+    let week_start = today_start - ChronoDuration::days(now.weekday().num_days_from_monday() as i64);
+
+    let mut today_minutes = 0.0;
+    let mut week_minutes = 0.0;
+    let mut all_time_minutes = 0.0;
+    let mut completed_sessions = 0u32;
+    let mut last_session = None;
+
+    for row in rows {
+        let (start_iso, minutes) = row?;
+        let start = match DateTime::parse_from_rfc3339(&start_iso) {
+            Ok(start) => start.with_timezone(&Utc),
+            Err(_) => continue,
+        };
+
+        all_time_minutes += minutes;
+        completed_sessions += 1;
+        if start >= week_start {
+            week_minutes += minutes;
+        }
+        if start >= today_start {
+            today_minutes += minutes;
+        }
+        if last_session.is_none_or(|last| start > last) {
+            last_session = Some(start);
+        }
+    }
+
+    let average_minutes = if completed_sessions > 0 {
+        all_time_minutes / completed_sessions as f32
+    } else {
+        0.0
+    };
+
+    Ok(Stats {
+        today_minutes,
+        week_minutes,
+        all_time_minutes,
+        completed_sessions,
+        average_minutes,
+        last_session,
+    })
+}
+
+/// Renders how long ago `from` was, e.g. "42 minute(s) ago" or "3 day(s) ago", or
+/// "just now" for less than a minute.
+fn time_ago(from: DateTime<Utc>) -> String {
+    let elapsed = Utc::now().signed_duration_since(from);
+    if elapsed.num_days() > 0 {
+        format!("{} day(s) ago", elapsed.num_days())
+    } else if elapsed.num_hours() > 0 {
+        format!("{} hour(s) ago", elapsed.num_hours())
+    } else if elapsed.num_minutes() > 0 {
+        format!("{} minute(s) ago", elapsed.num_minutes())
+    } else {
+        "just now".to_string()
+    }
+}
+
+/// Prints a colored summary of `stats` to stdout.
+fn print_stats(stats: &Stats) {
+    println!("{}\n", "Focus session stats".cyan().bold());
+    println!(
+        "  {} {:.1} min",
+        "Today:".green(),
+        stats.today_minutes
+    );
+    println!(
+        "  {} {:.1} min",
+        "This week:".green(),
+        stats.week_minutes
+    );
+    println!(
+        "  {} {:.1} min",
+        "All-time:".green(),
+        stats.all_time_minutes
+    );
+    println!(
+        "  {} {}",
+        "Completed sessions:".green(),
+        stats.completed_sessions
+    );
+    println!(
+        "  {} {:.1} min",
+        "Average session:".green(),
+        stats.average_minutes
+    );
+    match stats.last_session {
+        Some(last) => println!("  {} {}", "Most recent:".green(), time_ago(last).yellow()),
+        None => println!("  {} {}", "Most recent:".green(), "no sessions yet".yellow()),
+    }
+}
+
+/// Prints the daemon's reply to a control command, or a friendly error if no daemon
+/// is reachable.
+fn print_daemon_reply(reply: std::io::Result<String>) {
+    match reply {
+        Ok(reply) => println!("{}", reply.green()),
+        Err(e) => eprintln!("{}", format!("No running daemon to talk to: {e}").red()),
+    }
+}
+
+fn main() -> Result<()> {
+    let conn = Connection::open("focus.db")?;
+    init_db(&conn)?;
+
+    let args = Cli::parse();
+
+    match args.command {
+        Command::Focus {
+            duration,
+            tui,
+            sound_opts,
+        } => {
+            run_session(&conn, Mode::Focus, duration, tui, &sound_opts)?;
+        }
+        Command::Rest {
+            duration,
+            tui,
+            sound_opts,
+        } => {
+            run_session(&conn, Mode::Rest, duration, tui, &sound_opts)?;
+        }
+        Command::Cycle {
+            work,
+            short_rest,
+            long_rest,
+            cycles,
+            tui,
+            sound_opts,
+        } => run_cycle(&conn, work, short_rest, long_rest, cycles, tui, &sound_opts)?,
+        Command::Stats => print_stats(&compute_stats(&conn)?),
+        Command::Start { mode, duration } => {
+            let exe = std::env::current_exe().expect("failed to locate current executable");
+            std::process::Command::new(exe)
+                .arg("daemon-internal")
+                .arg(mode.as_str())
+                .arg(format!("{}s", duration.as_secs()))
+                .stdin(std::process::Stdio::null())
+                .stdout(std::process::Stdio::null())
+                .stderr(std::process::Stdio::null())
+                .spawn()
+                .expect("failed to spawn background daemon");
+            println!(
+                "{}",
+                format!(
+                    "Started a {} session in the background ({}s)",
+                    mode.as_str(),
+                    duration.as_secs()
+                )
+                .green()
+                .bold()
+            );
+        }
+        Command::Pause => print_daemon_reply(daemon::pause()),
+        Command::Resume => print_daemon_reply(daemon::resume()),
+        Command::Stop => print_daemon_reply(daemon::stop()),
+        Command::Status => print_daemon_reply(daemon::status()),
+        Command::DaemonInternal { mode, duration } => daemon::run_daemon(mode, duration)?,
+    }
+
     Ok(())
 }
 
+/// Plays the completion sound: `path` if given, otherwise the embedded default,
+/// appended to the sink `loop_count` times (at least once), blocking until every
+/// repetition has played. Does nothing if `silent` is set.
+fn play_sound(
+    path: Option<&std::path::Path>,
+    loop_count: u32,
+    silent: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if silent {
+        return Ok(());
+    }
 
-fn play_sound() -> Result<(), Box<dyn std::error::Error>> {
     let (_stream, stream_handle) = OutputStream::try_default()?;
-
     let sink = Sink::try_new(&stream_handle)?;
 
-    static SOUND: &[u8] = include_bytes!("../assets/sound.mp3");
-    let cursor = std::io::Cursor::new(SOUND);
-    let source = Decoder::new(cursor)?;
+    static DEFAULT_SOUND: &[u8] = include_bytes!("../assets/sound.mp3");
+    let bytes = match path {
+        Some(path) => std::fs::read(path)?,
+        None => DEFAULT_SOUND.to_vec(),
+    };
+
+    for _ in 0..loop_count.max(1) {
+        let cursor = std::io::Cursor::new(bytes.clone());
+        let source = Decoder::new(cursor)?;
+        sink.append(source);
+    }
 
-    sink.append(source);
     sink.sleep_until_end();
     Ok(())
 }
@@ -0,0 +1,138 @@
+//! Optional full-screen TUI front-end (`--tui`) for running a session, built on
+//! ratatui/crossterm. Renders a big-font countdown and a progress gauge in place of
+//! the line-based indicatif output, and lets the interval be paused, skipped, or
+//! quit without leaving the terminal.
+
+use crate::Mode;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use figlet_rs::FIGfont;
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Alignment, Constraint, Direction, Layout};
+use ratatui::style::{Color, Style};
+use ratatui::widgets::{Block, Borders, Gauge, Paragraph};
+use ratatui::Terminal;
+use std::io;
+use std::time::Duration;
+
+/// How the TUI countdown ended.
+pub enum Outcome {
+    Completed,
+    Skipped,
+    Quit,
+}
+
+/// The outcome of a TUI countdown, along with how much of `duration` actually
+/// elapsed (full on `Completed`, partial on `Skipped`/`Quit`).
+pub struct RunResult {
+    pub outcome: Outcome,
+    pub elapsed: Duration,
+}
+
+const TICK: Duration = Duration::from_millis(200);
+
+/// Restores the terminal to its normal mode on drop, so any early return (including
+/// via `?`) out of `run` can't leave the terminal stuck in raw/alternate-screen mode.
+struct TerminalGuard;
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = disable_raw_mode();
+        let _ = execute!(io::stdout(), LeaveAlternateScreen);
+    }
+}
+
+/// Runs a full-screen countdown for `duration` in `mode`, returning how it ended and
+/// how much time actually elapsed. While it's running, `p` toggles pause, `s` skips
+/// straight to completion (crediting the elapsed time, like letting it run out), and
+/// `q`/`Esc` quits without finishing.
+pub fn run(mode: Mode, duration: Duration) -> io::Result<RunResult> {
+    enable_raw_mode()?;
+    let _guard = TerminalGuard;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    let total = duration;
+    let mut remaining = duration;
+    let mut paused = false;
+    let font = FIGfont::standard().unwrap();
+
+    let outcome = loop {
+        terminal.draw(|f| draw(f, mode, remaining, total, paused, &font))?;
+
+        if remaining.is_zero() {
+            break Outcome::Completed;
+        }
+
+        if event::poll(TICK)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                match key.code {
+                    KeyCode::Char('p') => paused = !paused,
+                    KeyCode::Char('s') => break Outcome::Skipped,
+                    KeyCode::Char('q') | KeyCode::Esc => break Outcome::Quit,
+                    _ => {}
+                }
+            }
+        } else if !paused {
+            remaining = remaining.saturating_sub(TICK);
+        }
+    };
+
+    let elapsed = total.saturating_sub(remaining);
+    Ok(RunResult { outcome, elapsed })
+}
+
+fn draw(
+    f: &mut ratatui::Frame,
+    mode: Mode,
+    remaining: Duration,
+    total: Duration,
+    paused: bool,
+    font: &FIGfont,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(5),
+            Constraint::Length(3),
+        ])
+        .split(f.size());
+
+    let header = Paragraph::new(mode.as_str().to_uppercase())
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL))
+        .style(Style::default().fg(Color::Cyan));
+    f.render_widget(header, chunks[0]);
+
+    let mins = remaining.as_secs() / 60;
+    let secs = remaining.as_secs() % 60;
+    let clock = format!("{mins:02}:{secs:02}");
+    let big = font
+        .convert(&clock)
+        .map(|figure| figure.to_string())
+        .unwrap_or(clock);
+    let countdown = Paragraph::new(big)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(Color::Yellow));
+    f.render_widget(countdown, chunks[1]);
+
+    let ratio = 1.0 - (remaining.as_secs_f64() / total.as_secs_f64().max(1.0));
+    let gauge = Gauge::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(if paused { "Paused (p to resume)" } else { "Progress" }),
+        )
+        .gauge_style(Style::default().fg(Color::Blue))
+        .ratio(ratio.clamp(0.0, 1.0));
+    f.render_widget(gauge, chunks[2]);
+}